@@ -1,88 +1,247 @@
 use pyo3::prelude::*;
 use pyo3::exceptions::PyIOError;
 use jwalk::WalkDir;
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{Connection, params};
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
 use std::path::Path;
 use std::time::SystemTime;
 
-/// Initialize the database with tables and triggers
-fn init_db(conn: &Connection) -> rusqlite::Result<()> {
-    // Create main files table
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS files (
-            id INTEGER PRIMARY KEY,
-            path TEXT NOT NULL,
-            inode INTEGER,
-            mtime INTEGER,
-            size INTEGER
-        )",
-        [],
-    )?;
-
-    // Create FTS5 virtual table
-    conn.execute(
-        "CREATE VIRTUAL TABLE IF NOT EXISTS files_fts USING fts5(
-            path,
-            content='files',
-            content_rowid='id',
-            tokenize = 'unicode61'
-        )",
-        [],
-    )?;
-
-    // Create trigger for INSERT
-    conn.execute(
-        "CREATE TRIGGER IF NOT EXISTS files_ai AFTER INSERT ON files BEGIN
-            INSERT INTO files_fts(rowid, path) VALUES (new.id, new.path);
-        END",
-        [],
-    )?;
-
-    // Create trigger for DELETE
-    conn.execute(
-        "CREATE TRIGGER IF NOT EXISTS files_ad AFTER DELETE ON files BEGIN
-            INSERT INTO files_fts(files_fts, rowid, path)
-            VALUES('delete', old.id, old.path);
-        END",
-        [],
-    )?;
-
-    // Create trigger for UPDATE
-    conn.execute(
-        "CREATE TRIGGER IF NOT EXISTS files_au AFTER UPDATE ON files BEGIN
-            INSERT INTO files_fts(files_fts, rowid, path)
-            VALUES('delete', old.id, old.path);
-            INSERT INTO files_fts(rowid, path) VALUES (new.id, new.path);
-        END",
-        [],
-    )?;
+/// A single schema migration: the `user_version` it brings the database to,
+/// and the (possibly multi-statement) SQL that gets it there from the
+/// previous version.
+struct Migration {
+    version: i32,
+    sql: &'static str,
+}
+
+/// Ordered schema history, keyed off SQLite's `PRAGMA user_version`. Append
+/// new migrations here rather than editing an existing one in place, so
+/// databases created by older releases can still be upgraded transparently.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        sql: "
+            CREATE TABLE IF NOT EXISTS files (
+                id INTEGER PRIMARY KEY,
+                path TEXT NOT NULL,
+                inode INTEGER,
+                mtime INTEGER,
+                size INTEGER
+            );
+
+            CREATE VIRTUAL TABLE IF NOT EXISTS files_fts USING fts5(
+                path,
+                content='files',
+                content_rowid='id',
+                tokenize = 'unicode61'
+            );
+
+            CREATE TRIGGER IF NOT EXISTS files_ai AFTER INSERT ON files BEGIN
+                INSERT INTO files_fts(rowid, path) VALUES (new.id, new.path);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS files_ad AFTER DELETE ON files BEGIN
+                INSERT INTO files_fts(files_fts, rowid, path)
+                VALUES('delete', old.id, old.path);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS files_au AFTER UPDATE ON files BEGIN
+                INSERT INTO files_fts(files_fts, rowid, path)
+                VALUES('delete', old.id, old.path);
+                INSERT INTO files_fts(rowid, path) VALUES (new.id, new.path);
+            END;
+        ",
+    },
+    Migration {
+        // Widen the schema to optionally carry file contents alongside paths,
+        // so `files_fts` can be searched by what's inside a file, not just
+        // its name. The FTS5 table has to be dropped and rebuilt with the
+        // extra column, since its shape can't be altered in place.
+        version: 2,
+        sql: "
+            ALTER TABLE files ADD COLUMN content TEXT;
+
+            DROP TRIGGER IF EXISTS files_ai;
+            DROP TRIGGER IF EXISTS files_ad;
+            DROP TRIGGER IF EXISTS files_au;
+            DROP TABLE IF EXISTS files_fts;
+
+            CREATE VIRTUAL TABLE files_fts USING fts5(
+                path,
+                content,
+                content='files',
+                content_rowid='id',
+                tokenize = 'unicode61'
+            );
+
+            INSERT INTO files_fts(rowid, path, content) SELECT id, path, content FROM files;
+
+            CREATE TRIGGER files_ai AFTER INSERT ON files BEGIN
+                INSERT INTO files_fts(rowid, path, content) VALUES (new.id, new.path, new.content);
+            END;
+
+            CREATE TRIGGER files_ad AFTER DELETE ON files BEGIN
+                INSERT INTO files_fts(files_fts, rowid, path, content)
+                VALUES('delete', old.id, old.path, old.content);
+            END;
+
+            CREATE TRIGGER files_au AFTER UPDATE ON files BEGIN
+                INSERT INTO files_fts(files_fts, rowid, path, content)
+                VALUES('delete', old.id, old.path, old.content);
+                INSERT INTO files_fts(rowid, path, content) VALUES (new.id, new.path, new.content);
+            END;
+        ",
+    },
+    Migration {
+        // Tag every row with a coarse "kind" (directory/symlink/regular, or a
+        // content category for regular files) so callers can restrict
+        // searches and stats by type instead of scanning everything.
+        version: 3,
+        sql: "
+            ALTER TABLE files ADD COLUMN kind TEXT;
+            CREATE INDEX IF NOT EXISTS idx_files_kind ON files(kind);
+        ",
+    },
+];
+
+/// Bring the database up to the latest known schema, applying every pending
+/// migration in order and bumping `user_version` after each one. Errors if
+/// the on-disk version is newer than this build of the crate understands.
+fn run_migrations(conn: &mut Connection) -> Result<(), String> {
+    let current_version: i32 = conn
+        .query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(|e| format!("Failed to read schema version: {}", e))?;
+
+    let latest_version = MIGRATIONS.iter().map(|m| m.version).max().unwrap_or(0);
+
+    if current_version > latest_version {
+        return Err(format!(
+            "Database schema version {} is newer than this version of pylocate supports (up to {})",
+            current_version, latest_version
+        ));
+    }
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current_version) {
+        // Each step runs in its own transaction so a failure partway through
+        // (either the DDL batch or the version bump) leaves the database
+        // exactly where it started instead of landing between versions.
+        let tx = conn.transaction()
+            .map_err(|e| format!("Failed to start migration transaction: {}", e))?;
+        tx.execute_batch(migration.sql)
+            .map_err(|e| format!("Failed to apply migration to version {}: {}", migration.version, e))?;
+        tx.pragma_update(None, "user_version", migration.version)
+            .map_err(|e| format!("Failed to record schema version {}: {}", migration.version, e))?;
+        tx.commit()
+            .map_err(|e| format!("Failed to commit migration to version {}: {}", migration.version, e))?;
+    }
 
     Ok(())
 }
 
-/// Index a directory and store results in SQLite
+/// Upgrade a database file to the latest schema, for callers that want to
+/// migrate explicitly rather than relying on it happening implicitly on the
+/// next index/search/stats call.
 #[pyfunction]
-fn index_directory(db_path: String, root_paths: Vec<String>) -> PyResult<usize> {
+fn migrate(db_path: String) -> PyResult<()> {
     let mut conn = Connection::open(&db_path)
         .map_err(|e| PyIOError::new_err(format!("Failed to open database: {}", e)))?;
 
-    init_db(&conn)
-        .map_err(|e| PyIOError::new_err(format!("Failed to initialize database: {}", e)))?;
+    run_migrations(&mut conn).map_err(PyIOError::new_err)
+}
+
+/// Controls whether `index_directory`/`update_directory` also index file
+/// contents, and which files are eligible: only extensions in `extensions`
+/// are read, and at most `max_bytes` is read from each (streamed rather than
+/// loaded whole, so memory stays bounded on large trees).
+struct ContentIndexOptions {
+    enabled: bool,
+    extensions: HashSet<String>,
+    max_bytes: usize,
+}
+
+const DEFAULT_MAX_CONTENT_BYTES: usize = 1_048_576;
+
+fn default_content_extensions() -> HashSet<String> {
+    [
+        "txt", "md", "rs", "py", "js", "ts", "json", "toml", "yaml", "yml",
+        "c", "cpp", "h", "hpp", "java", "go", "rb", "sh", "html", "css",
+        "xml", "ini", "cfg", "log",
+    ].iter().map(|s| s.to_string()).collect()
+}
+
+fn content_index_options(
+    index_content: Option<bool>,
+    content_extensions: Option<Vec<String>>,
+    max_content_bytes: Option<usize>,
+) -> ContentIndexOptions {
+    ContentIndexOptions {
+        enabled: index_content.unwrap_or(false),
+        extensions: content_extensions
+            .map(|exts| exts.into_iter().map(|e| e.trim_start_matches('.').to_lowercase()).collect())
+            .unwrap_or_else(default_content_extensions),
+        max_bytes: max_content_bytes.unwrap_or(DEFAULT_MAX_CONTENT_BYTES),
+    }
+}
+
+/// Read up to `opts.max_bytes` of `path`'s content if content indexing is
+/// enabled, the extension is allowlisted, and the bytes sniff as UTF-8 text
+/// rather than binary.
+fn read_indexable_content(path: &Path, opts: &ContentIndexOptions) -> Option<String> {
+    if !opts.enabled {
+        return None;
+    }
+
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    if !opts.extensions.contains(&ext) {
+        return None;
+    }
 
+    let file = std::fs::File::open(path).ok()?;
+    let mut buf = Vec::with_capacity(opts.max_bytes.min(64 * 1024));
+    file.take(opts.max_bytes as u64).read_to_end(&mut buf).ok()?;
+
+    if buf.contains(&0u8) {
+        // Binary sniff: a NUL byte this early means it's not text.
+        return None;
+    }
+
+    // The read may have truncated a multi-byte character at `max_bytes`;
+    // trim back to the last valid boundary instead of discarding everything.
+    match std::str::from_utf8(&buf) {
+        Ok(content) => Some(content.to_string()),
+        Err(e) if e.error_len().is_none() => {
+            buf.truncate(e.valid_up_to());
+            String::from_utf8(buf).ok()
+        }
+        // An invalid sequence before the end of the read is not a
+        // truncation artifact: the file isn't UTF-8 text.
+        Err(_) => None,
+    }
+}
+
+/// Walk `root_paths` and (re)build the `files` table from scratch.
+fn do_index_directory(
+    conn: &mut Connection,
+    root_paths: &[String],
+    content_opts: &ContentIndexOptions,
+) -> Result<usize, String> {
     // Clear existing data
     conn.execute("DELETE FROM files", [])
-        .map_err(|e| PyIOError::new_err(format!("Failed to clear database: {}", e)))?;
+        .map_err(|e| format!("Failed to clear database: {}", e))?;
 
     let tx = conn.transaction()
-        .map_err(|e| PyIOError::new_err(format!("Failed to start transaction: {}", e)))?;
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
 
     let mut count = 0usize;
     let mut stmt = tx.prepare(
-        "INSERT INTO files (path, inode, mtime, size) VALUES (?1, ?2, ?3, ?4)"
-    ).map_err(|e| PyIOError::new_err(format!("Failed to prepare statement: {}", e)))?;
+        "INSERT INTO files (path, inode, mtime, size, content, kind) VALUES (?1, ?2, ?3, ?4, ?5, ?6)"
+    ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
 
     for root_path in root_paths {
-        for entry in WalkDir::new(&root_path)
+        for entry in WalkDir::new(root_path)
             .skip_hidden(false)
             .follow_links(false)
         {
@@ -99,8 +258,10 @@ fn index_directory(db_path: String, root_paths: Vec<String>) -> PyResult<usize>
                             .map(|d| d.as_secs() as i64)
                             .unwrap_or(0);
                         let size = metadata.len() as i64;
+                        let content = read_indexable_content(&path, content_opts);
+                        let kind = classify_kind(&path, &metadata);
 
-                        if stmt.execute(params![path_str, inode, mtime, size]).is_ok() {
+                        if stmt.execute(params![path_str, inode, mtime, size, content, kind]).is_ok() {
                             count += 1;
                         }
                     }
@@ -112,11 +273,174 @@ fn index_directory(db_path: String, root_paths: Vec<String>) -> PyResult<usize>
 
     drop(stmt);
     tx.commit()
-        .map_err(|e| PyIOError::new_err(format!("Failed to commit transaction: {}", e)))?;
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
 
     Ok(count)
 }
 
+/// Index a directory and store results in SQLite
+#[pyfunction]
+fn index_directory(
+    db_path: String,
+    root_paths: Vec<String>,
+    index_content: Option<bool>,
+    content_extensions: Option<Vec<String>>,
+    max_content_bytes: Option<usize>,
+) -> PyResult<usize> {
+    let mut conn = Connection::open(&db_path)
+        .map_err(|e| PyIOError::new_err(format!("Failed to open database: {}", e)))?;
+
+    run_migrations(&mut conn).map_err(PyIOError::new_err)?;
+
+    let content_opts = content_index_options(index_content, content_extensions, max_content_bytes);
+    do_index_directory(&mut conn, &root_paths, &content_opts).map_err(PyIOError::new_err)
+}
+
+/// Incrementally re-index one or more roots instead of rebuilding from scratch.
+///
+/// Existing rows under each root are loaded into memory keyed by path, then
+/// diffed against what `WalkDir` observes on disk: unseen paths are inserted,
+/// paths whose `mtime`/`size` drifted are updated, unchanged paths are left
+/// alone, and rows that were never seen during the walk are deleted as gone.
+/// Returns `(added, updated, deleted, unchanged)` counts.
+#[pyfunction]
+fn update_directory(
+    db_path: String,
+    root_paths: Vec<String>,
+    index_content: Option<bool>,
+    content_extensions: Option<Vec<String>>,
+    max_content_bytes: Option<usize>,
+) -> PyResult<(usize, usize, usize, usize)> {
+    let mut conn = Connection::open(&db_path)
+        .map_err(|e| PyIOError::new_err(format!("Failed to open database: {}", e)))?;
+
+    run_migrations(&mut conn).map_err(PyIOError::new_err)?;
+
+    let content_opts = content_index_options(index_content, content_extensions, max_content_bytes);
+    do_update_directory(&mut conn, &root_paths, &content_opts).map_err(PyIOError::new_err)
+}
+
+fn do_update_directory(
+    conn: &mut Connection,
+    root_paths: &[String],
+    content_opts: &ContentIndexOptions,
+) -> Result<(usize, usize, usize, usize), String> {
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let mut added = 0usize;
+    let mut updated = 0usize;
+    let mut deleted = 0usize;
+    let mut unchanged = 0usize;
+
+    for root_path in root_paths {
+        // Only rows under this root are eligible for deletion, so scope the
+        // initial load with a LIKE prefix rather than scanning the whole
+        // table. The prefix is anchored at a path boundary (exact match or
+        // `root/...`) so a sibling like `root-backup` never gets swept in.
+        let mut existing: HashMap<String, (i64, i64, i64)> = HashMap::new();
+        {
+            let escaped_root = root_path.trim_end_matches('/')
+                .replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+            let like_root = format!("{}/%", escaped_root);
+            let mut stmt = tx.prepare(
+                "SELECT id, path, mtime, size FROM files WHERE path = ?1 OR path LIKE ?2 ESCAPE '\\'"
+            ).map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+            let rows = stmt.query_map(params![root_path.trim_end_matches('/'), like_root], |row| {
+                let id: i64 = row.get(0)?;
+                let path: String = row.get(1)?;
+                let mtime: i64 = row.get(2)?;
+                let size: i64 = row.get(3)?;
+                Ok((path, (id, mtime, size)))
+            }).map_err(|e| format!("Failed to load existing rows: {}", e))?;
+
+            for row in rows {
+                let (path, data) = row.map_err(|e| format!("Failed to read row: {}", e))?;
+                existing.insert(path, data);
+            }
+        }
+
+        let mut seen: HashSet<i64> = HashSet::new();
+
+        {
+            let mut insert_stmt = tx.prepare(
+                "INSERT INTO files (path, inode, mtime, size, content, kind) VALUES (?1, ?2, ?3, ?4, ?5, ?6)"
+            ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+            let mut update_stmt = tx.prepare(
+                "UPDATE files SET inode = ?1, mtime = ?2, size = ?3, content = ?4, kind = ?5 WHERE id = ?6"
+            ).map_err(|e| format!("Failed to prepare statement: {}", e))?;
+
+            for entry in WalkDir::new(root_path)
+                .skip_hidden(false)
+                .follow_links(false)
+            {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(_) => continue,
+                };
+                let path = entry.path();
+                let path_str = path.to_string_lossy().to_string();
+
+                let metadata = match entry.metadata() {
+                    Ok(metadata) => metadata,
+                    Err(_) => continue,
+                };
+
+                let inode = get_inode(&metadata);
+                let mtime = metadata.modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs() as i64)
+                    .unwrap_or(0);
+                let size = metadata.len() as i64;
+
+                match existing.get(&path_str) {
+                    Some(&(id, old_mtime, old_size)) => {
+                        seen.insert(id);
+                        if old_mtime != mtime || old_size != size {
+                            let content = read_indexable_content(&path, content_opts);
+                            let kind = classify_kind(&path, &metadata);
+                            update_stmt.execute(params![inode, mtime, size, content, kind, id])
+                                .map_err(|e| format!("Failed to update row: {}", e))?;
+                            updated += 1;
+                        } else {
+                            unchanged += 1;
+                        }
+                    }
+                    None => {
+                        let content = read_indexable_content(&path, content_opts);
+                        let kind = classify_kind(&path, &metadata);
+                        insert_stmt.execute(params![path_str, inode, mtime, size, content, kind])
+                            .map_err(|e| format!("Failed to insert row: {}", e))?;
+                        added += 1;
+                    }
+                }
+            }
+        }
+
+        // Anything never marked seen during the walk is gone from disk.
+        let stale: Vec<i64> = existing.values()
+            .map(|&(id, _, _)| id)
+            .filter(|id| !seen.contains(id))
+            .collect();
+
+        if !stale.is_empty() {
+            let placeholders = stale.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+            let sql = format!("DELETE FROM files WHERE id IN ({})", placeholders);
+            let params_vec: Vec<&dyn rusqlite::ToSql> = stale.iter().map(|id| id as &dyn rusqlite::ToSql).collect();
+            tx.execute(&sql, params_vec.as_slice())
+                .map_err(|e| format!("Failed to delete stale rows: {}", e))?;
+            deleted += stale.len();
+        }
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit transaction: {}", e))?;
+
+    Ok((added, updated, deleted, unchanged))
+}
+
 #[cfg(unix)]
 fn get_inode(metadata: &std::fs::Metadata) -> i64 {
     use std::os::unix::fs::MetadataExt;
@@ -128,67 +452,506 @@ fn get_inode(_metadata: &std::fs::Metadata) -> i64 {
     0
 }
 
-/// Search for files matching a pattern
-#[pyfunction]
-fn search_files(db_path: String, pattern: String, limit: Option<usize>) -> PyResult<Vec<String>> {
-    let conn = Connection::open(&db_path)
-        .map_err(|e| PyIOError::new_err(format!("Failed to open database: {}", e)))?;
+/// Classify an entry as "directory", "symlink", or a content category
+/// inferred from its extension (falling back to "file" for anything
+/// unrecognized).
+fn classify_kind(path: &Path, metadata: &std::fs::Metadata) -> String {
+    if metadata.is_dir() {
+        return "directory".to_string();
+    }
+    if metadata.file_type().is_symlink() {
+        return "symlink".to_string();
+    }
+
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    match ext.as_str() {
+        "jpg" | "jpeg" | "png" | "gif" | "bmp" | "svg" | "webp" | "tiff" => "image",
+        "mp4" | "mkv" | "avi" | "mov" | "webm" | "flv" => "video",
+        "mp3" | "wav" | "flac" | "ogg" | "m4a" | "aac" => "audio",
+        "zip" | "tar" | "gz" | "bz2" | "7z" | "rar" | "xz" => "archive",
+        "pdf" | "doc" | "docx" | "xls" | "xlsx" | "ppt" | "pptx" | "odt" => "document",
+        "rs" | "py" | "js" | "ts" | "go" | "c" | "cpp" | "h" | "hpp" | "java" | "rb" | "sh" | "html" | "css" => "code",
+        "txt" | "md" | "log" | "json" | "toml" | "yaml" | "yml" | "xml" | "ini" | "cfg" => "text",
+        _ => "file",
+    }.to_string()
+}
+
+/// Which FTS5 column(s) a full-text query targets. Ignored for glob/LIKE
+/// patterns, which always match against `path`.
+fn fts_match_target(scope: &str) -> &'static str {
+    match scope {
+        "content" => "files_fts.content",
+        "both" => "files_fts",
+        _ => "files_fts.path",
+    }
+}
+
+fn do_search_files(conn: &Connection, pattern: &str, limit: Option<usize>, scope: &str, kinds: &[String]) -> Result<Vec<String>, String> {
+    let kind_clause = if kinds.is_empty() {
+        String::new()
+    } else {
+        let placeholders = kinds.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+        format!(" AND kind IN ({})", placeholders)
+    };
 
     let query = if pattern.contains('*') || pattern.contains('?') {
         // Use LIKE for glob patterns
-        let like_pattern = pattern.replace('*', "%").replace('?', "_");
-        format!("SELECT path FROM files WHERE path LIKE ? ESCAPE '\\' ORDER BY path LIMIT ?")
+        format!("SELECT path FROM files WHERE path LIKE ? ESCAPE '\\'{} ORDER BY path LIMIT ?", kind_clause)
     } else {
-        // Use FTS5 for full-text search
-        format!("SELECT files.path FROM files_fts 
-                 JOIN files ON files_fts.rowid = files.id 
-                 WHERE files_fts MATCH ? 
-                 ORDER BY rank LIMIT ?")
+        // Use FTS5 for full-text search, scoped to the requested column(s)
+        format!("SELECT files.path FROM files_fts
+                 JOIN files ON files_fts.rowid = files.id
+                 WHERE {} MATCH ?{}
+                 ORDER BY rank LIMIT ?", fts_match_target(scope), kind_clause)
     };
 
-    let limit_val = limit.unwrap_or(1000) as i64;
-    let mut stmt = conn.prepare(&query)
-        .map_err(|e| PyIOError::new_err(format!("Failed to prepare query: {}", e)))?;
-
     let search_pattern = if pattern.contains('*') || pattern.contains('?') {
         pattern.replace('*', "%").replace('?', "_")
     } else {
-        format!("*{}*", pattern)
+        // A leading `*` is parsed as an FTS5 special query, not a wildcard, so
+        // this can only be a trailing prefix match.
+        format!("{}*", pattern)
     };
+    let limit_val = limit.unwrap_or(1000) as i64;
+
+    let mut param_refs: Vec<&dyn rusqlite::ToSql> = vec![&search_pattern];
+    for kind in kinds {
+        param_refs.push(kind);
+    }
+    param_refs.push(&limit_val);
 
-    let results = stmt.query_map(params![search_pattern, limit_val], |row| {
+    let mut stmt = conn.prepare(&query)
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let results = stmt.query_map(param_refs.as_slice(), |row| {
         row.get::<_, String>(0)
     })
-    .map_err(|e| PyIOError::new_err(format!("Failed to execute query: {}", e)))?
+    .map_err(|e| format!("Failed to execute query: {}", e))?
+    .filter_map(|r| r.ok())
+    .collect();
+
+    Ok(results)
+}
+
+/// Search for files matching a pattern. `scope` is `"path"` (default),
+/// `"content"`, or `"both"`, and targets the FTS5 query at the matching
+/// column(s); it has no effect on glob/LIKE patterns, which always match
+/// against `path`. `kinds`, if given, restricts results to rows whose `kind`
+/// is in the list (see [`classify_kind`]).
+#[pyfunction]
+fn search_files(
+    db_path: String,
+    pattern: String,
+    limit: Option<usize>,
+    scope: Option<String>,
+    kinds: Option<Vec<String>>,
+) -> PyResult<Vec<String>> {
+    let mut conn = Connection::open(&db_path)
+        .map_err(|e| PyIOError::new_err(format!("Failed to open database: {}", e)))?;
+
+    run_migrations(&mut conn).map_err(PyIOError::new_err)?;
+
+    do_search_files(&conn, &pattern, limit, scope.as_deref().unwrap_or("path"), &kinds.unwrap_or_default())
+        .map_err(PyIOError::new_err)
+}
+
+/// Column to sort structured query results by. `Rank` only makes sense when
+/// a full-text `pattern` is supplied; it falls back to `Path` otherwise.
+fn sort_column(sort_by: &str, is_fts_query: bool) -> &'static str {
+    match sort_by {
+        "size" => "files.size",
+        "mtime" => "files.mtime",
+        "rank" if is_fts_query => "rank",
+        _ => "files.path",
+    }
+}
+
+/// Filter/sort knobs for `do_query_files`, bundled the same way
+/// `ContentIndexOptions` bundles the content-indexing knobs: the Python-facing
+/// `query_files` has too many independently optional parameters to thread
+/// through a query builder positionally.
+struct QueryFilters {
+    scope: String,
+    kinds: Vec<String>,
+    min_size: Option<i64>,
+    max_size: Option<i64>,
+    modified_after: Option<i64>,
+    modified_before: Option<i64>,
+    sort_by: String,
+    ascending: bool,
+    limit: Option<usize>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn query_filters(
+    scope: Option<String>,
+    kinds: Option<Vec<String>>,
+    min_size: Option<i64>,
+    max_size: Option<i64>,
+    modified_after: Option<i64>,
+    modified_before: Option<i64>,
+    sort_by: Option<String>,
+    ascending: Option<bool>,
+    limit: Option<usize>,
+) -> QueryFilters {
+    QueryFilters {
+        scope: scope.unwrap_or_else(|| "path".to_string()),
+        kinds: kinds.unwrap_or_default(),
+        min_size,
+        max_size,
+        modified_after,
+        modified_before,
+        sort_by: sort_by.unwrap_or_else(|| "path".to_string()),
+        ascending: ascending.unwrap_or(true),
+        limit,
+    }
+}
+
+/// Build and run a structured query over `files`, returning
+/// `(path, size, mtime, inode)` rows instead of bare paths.
+fn do_query_files(
+    conn: &Connection,
+    pattern: Option<&str>,
+    filters: &QueryFilters,
+) -> Result<Vec<(String, i64, i64, i64)>, String> {
+    let is_glob = pattern.is_some_and(|p| p.contains('*') || p.contains('?'));
+    let is_fts_query = pattern.is_some() && !is_glob;
+
+    let mut conditions: Vec<String> = Vec::new();
+    let mut param_refs: Vec<&dyn rusqlite::ToSql> = Vec::new();
+
+    let like_pattern;
+    let fts_pattern;
+    if let Some(p) = pattern {
+        if is_glob {
+            like_pattern = p.replace('*', "%").replace('?', "_");
+            conditions.push("files.path LIKE ? ESCAPE '\\'".to_string());
+            param_refs.push(&like_pattern);
+        } else {
+            // A leading `*` is parsed as an FTS5 special query, not a
+            // wildcard, so this can only be a trailing prefix match.
+            fts_pattern = format!("{}*", p);
+            conditions.push(format!("{} MATCH ?", fts_match_target(&filters.scope)));
+            param_refs.push(&fts_pattern);
+        }
+    }
+
+    if let Some(min_size) = filters.min_size.as_ref() {
+        conditions.push("files.size >= ?".to_string());
+        param_refs.push(min_size);
+    }
+    if let Some(max_size) = filters.max_size.as_ref() {
+        conditions.push("files.size <= ?".to_string());
+        param_refs.push(max_size);
+    }
+    if let Some(modified_after) = filters.modified_after.as_ref() {
+        conditions.push("files.mtime >= ?".to_string());
+        param_refs.push(modified_after);
+    }
+    if let Some(modified_before) = filters.modified_before.as_ref() {
+        conditions.push("files.mtime <= ?".to_string());
+        param_refs.push(modified_before);
+    }
+
+    let placeholders = filters.kinds.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+    if !filters.kinds.is_empty() {
+        conditions.push(format!("files.kind IN ({})", placeholders));
+        for kind in &filters.kinds {
+            param_refs.push(kind);
+        }
+    }
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", conditions.join(" AND "))
+    };
+
+    let from_clause = if is_fts_query {
+        "files_fts JOIN files ON files_fts.rowid = files.id"
+    } else {
+        "files"
+    };
+
+    let direction = if filters.ascending { "ASC" } else { "DESC" };
+    let limit_val = filters.limit.unwrap_or(1000) as i64;
+    param_refs.push(&limit_val);
+
+    let query = format!(
+        "SELECT files.path, files.size, files.mtime, files.inode FROM {}{} ORDER BY {} {} LIMIT ?",
+        from_clause, where_clause, sort_column(&filters.sort_by, is_fts_query), direction
+    );
+
+    let mut stmt = conn.prepare(&query)
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let results = stmt.query_map(param_refs.as_slice(), |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?, row.get::<_, i64>(3)?))
+    })
+    .map_err(|e| format!("Failed to execute query: {}", e))?
     .filter_map(|r| r.ok())
     .collect();
 
     Ok(results)
 }
 
-/// Get database statistics
+/// A richer query entry point than `search_files`: returns structured
+/// `(path, size, mtime, inode)` rows and accepts filter predicates the way a
+/// catalog selector does. `pattern` is optional and, like `search_files`, may
+/// be a glob or a full-text query; `scope`/`kinds` behave the same as in
+/// `search_files`. `min_size`/`max_size` are in bytes and
+/// `modified_after`/`modified_before` are Unix seconds; any of them may be
+/// omitted. `sort_by` is one of `"path"` (default), `"size"`, `"mtime"`, or
+/// `"rank"` (only meaningful alongside a full-text `pattern`), and
+/// `ascending` defaults to `true`.
+#[allow(clippy::too_many_arguments)] // mirrors Python's flat kwarg surface; see QueryFilters
 #[pyfunction]
-fn get_stats(db_path: String) -> PyResult<(usize, i64)> {
-    let conn = Connection::open(&db_path)
+fn query_files(
+    db_path: String,
+    pattern: Option<String>,
+    scope: Option<String>,
+    kinds: Option<Vec<String>>,
+    min_size: Option<i64>,
+    max_size: Option<i64>,
+    modified_after: Option<i64>,
+    modified_before: Option<i64>,
+    sort_by: Option<String>,
+    ascending: Option<bool>,
+    limit: Option<usize>,
+) -> PyResult<Vec<(String, i64, i64, i64)>> {
+    let mut conn = Connection::open(&db_path)
         .map_err(|e| PyIOError::new_err(format!("Failed to open database: {}", e)))?;
 
-    let count: usize = conn.query_row(
-        "SELECT COUNT(*) FROM files",
-        [],
-        |row| row.get(0)
-    ).unwrap_or(0);
+    run_migrations(&mut conn).map_err(PyIOError::new_err)?;
+
+    let filters = query_filters(
+        scope, kinds, min_size, max_size, modified_after, modified_before, sort_by, ascending, limit,
+    );
+    do_query_files(&conn, pattern.as_deref(), &filters).map_err(PyIOError::new_err)
+}
+
+/// Per-kind (count, total size in bytes) breakdown of the indexed files.
+fn do_get_stats(conn: &Connection) -> Result<Vec<(String, usize, i64)>, String> {
+    let mut stmt = conn.prepare(
+        "SELECT COALESCE(kind, 'unknown'), COUNT(*), COALESCE(SUM(size), 0)
+         FROM files GROUP BY kind ORDER BY kind"
+    ).map_err(|e| format!("Failed to prepare query: {}", e))?;
+
+    let rows = stmt.query_map([], |row| {
+        let kind: String = row.get(0)?;
+        let count: usize = row.get(1)?;
+        let total_size: i64 = row.get(2)?;
+        Ok((kind, count, total_size))
+    }).map_err(|e| format!("Failed to execute query: {}", e))?;
+
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
+
+/// Get a per-kind breakdown of the indexed files: `(kind, count, total_size)`
+/// rows, one per distinct [`classify_kind`] value. Rows with no recorded kind
+/// (indexed before the `kind` column existed) are grouped as `"unknown"`.
+#[pyfunction]
+fn get_stats(db_path: String) -> PyResult<Vec<(String, usize, i64)>> {
+    let mut conn = Connection::open(&db_path)
+        .map_err(|e| PyIOError::new_err(format!("Failed to open database: {}", e)))?;
+
+    run_migrations(&mut conn).map_err(PyIOError::new_err)?;
+
+    do_get_stats(&conn).map_err(PyIOError::new_err)
+}
+
+/// A reusable handle onto a pylocate database, backed by a pooled set of
+/// SQLite connections instead of opening one fresh per call. Readers run
+/// under WAL with a busy-timeout so searches don't block behind an
+/// in-progress index, and the GIL is released around the blocking SQLite
+/// work so multiple Python threads can actually run in parallel.
+#[pyclass]
+struct PyLocateDb {
+    pool: Pool<SqliteConnectionManager>,
+}
+
+#[pymethods]
+impl PyLocateDb {
+    #[new]
+    fn new(db_path: String) -> PyResult<Self> {
+        let manager = SqliteConnectionManager::file(&db_path)
+            .with_init(|conn| {
+                conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA busy_timeout = 5000;")
+            });
+        let pool = Pool::builder()
+            .build(manager)
+            .map_err(|e| PyIOError::new_err(format!("Failed to create connection pool: {}", e)))?;
+
+        {
+            let mut conn = pool.get()
+                .map_err(|e| PyIOError::new_err(format!("Failed to check out connection: {}", e)))?;
+            run_migrations(&mut conn).map_err(PyIOError::new_err)?;
+        }
+
+        Ok(PyLocateDb { pool })
+    }
+
+    fn index_directory(
+        &self,
+        py: Python,
+        root_paths: Vec<String>,
+        index_content: Option<bool>,
+        content_extensions: Option<Vec<String>>,
+        max_content_bytes: Option<usize>,
+    ) -> PyResult<usize> {
+        let content_opts = content_index_options(index_content, content_extensions, max_content_bytes);
+        py.allow_threads(|| {
+            let mut conn = self.pool.get()
+                .map_err(|e| PyIOError::new_err(format!("Failed to check out connection: {}", e)))?;
+            do_index_directory(&mut conn, &root_paths, &content_opts).map_err(PyIOError::new_err)
+        })
+    }
+
+    fn update_directory(
+        &self,
+        py: Python,
+        root_paths: Vec<String>,
+        index_content: Option<bool>,
+        content_extensions: Option<Vec<String>>,
+        max_content_bytes: Option<usize>,
+    ) -> PyResult<(usize, usize, usize, usize)> {
+        let content_opts = content_index_options(index_content, content_extensions, max_content_bytes);
+        py.allow_threads(|| {
+            let mut conn = self.pool.get()
+                .map_err(|e| PyIOError::new_err(format!("Failed to check out connection: {}", e)))?;
+            do_update_directory(&mut conn, &root_paths, &content_opts).map_err(PyIOError::new_err)
+        })
+    }
+
+    fn search_files(
+        &self,
+        py: Python,
+        pattern: String,
+        limit: Option<usize>,
+        scope: Option<String>,
+        kinds: Option<Vec<String>>,
+    ) -> PyResult<Vec<String>> {
+        let scope = scope.unwrap_or_else(|| "path".to_string());
+        let kinds = kinds.unwrap_or_default();
+        py.allow_threads(|| {
+            let conn = self.pool.get()
+                .map_err(|e| PyIOError::new_err(format!("Failed to check out connection: {}", e)))?;
+            do_search_files(&conn, &pattern, limit, &scope, &kinds).map_err(PyIOError::new_err)
+        })
+    }
 
-    let size: i64 = std::fs::metadata(&db_path)
-        .map(|m| m.len() as i64)
-        .unwrap_or(0);
+    #[allow(clippy::too_many_arguments)] // mirrors Python's flat kwarg surface; see QueryFilters
+    fn query_files(
+        &self,
+        py: Python,
+        pattern: Option<String>,
+        scope: Option<String>,
+        kinds: Option<Vec<String>>,
+        min_size: Option<i64>,
+        max_size: Option<i64>,
+        modified_after: Option<i64>,
+        modified_before: Option<i64>,
+        sort_by: Option<String>,
+        ascending: Option<bool>,
+        limit: Option<usize>,
+    ) -> PyResult<Vec<(String, i64, i64, i64)>> {
+        let filters = query_filters(
+            scope, kinds, min_size, max_size, modified_after, modified_before, sort_by, ascending, limit,
+        );
+        py.allow_threads(|| {
+            let conn = self.pool.get()
+                .map_err(|e| PyIOError::new_err(format!("Failed to check out connection: {}", e)))?;
+            do_query_files(&conn, pattern.as_deref(), &filters).map_err(PyIOError::new_err)
+        })
+    }
 
-    Ok((count, size))
+    fn get_stats(&self, py: Python) -> PyResult<Vec<(String, usize, i64)>> {
+        py.allow_threads(|| {
+            let conn = self.pool.get()
+                .map_err(|e| PyIOError::new_err(format!("Failed to check out connection: {}", e)))?;
+            do_get_stats(&conn).map_err(PyIOError::new_err)
+        })
+    }
 }
 
 #[pymodule]
 fn pylocate_rust(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(migrate, m)?)?;
     m.add_function(wrap_pyfunction!(index_directory, m)?)?;
+    m.add_function(wrap_pyfunction!(update_directory, m)?)?;
     m.add_function(wrap_pyfunction!(search_files, m)?)?;
+    m.add_function(wrap_pyfunction!(query_files, m)?)?;
     m.add_function(wrap_pyfunction!(get_stats, m)?)?;
+    m.add_class::<PyLocateDb>()?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_db() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        run_migrations(&mut conn).unwrap();
+        conn
+    }
+
+    fn insert_file(conn: &Connection, path: &str, content: Option<&str>, kind: &str) {
+        conn.execute(
+            "INSERT INTO files (path, inode, mtime, size, content, kind) VALUES (?1, 0, 0, 0, ?2, ?3)",
+            params![path, content, kind],
+        ).unwrap();
+    }
+
+    #[test]
+    fn search_files_matches_plain_keyword() {
+        let conn = test_db();
+        insert_file(&conn, "/data/hello.txt", None, "text");
+
+        let results = do_search_files(&conn, "hello", None, "path", &[]).unwrap();
+        assert_eq!(results, vec!["/data/hello.txt".to_string()]);
+    }
+
+    #[test]
+    fn search_files_content_scope_respects_kind_filter() {
+        let conn = test_db();
+        insert_file(&conn, "/pics/vacation.jpg", Some("beach vacation photo"), "image");
+        insert_file(&conn, "/notes/vacation.txt", Some("vacation itinerary"), "text");
+
+        let kinds = vec!["image".to_string()];
+        let results = do_search_files(&conn, "vacation", None, "content", &kinds).unwrap();
+        assert_eq!(results, vec!["/pics/vacation.jpg".to_string()]);
+    }
+
+    #[test]
+    fn query_files_matches_plain_keyword_sorted_by_rank() {
+        let conn = test_db();
+        insert_file(&conn, "/data/hello.txt", None, "text");
+
+        let filters = query_filters(
+            None, None, None, None, None, None, Some("rank".to_string()), None, None,
+        );
+        let results = do_query_files(&conn, Some("hello"), &filters).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "/data/hello.txt");
+    }
+
+    #[test]
+    fn read_indexable_content_trims_truncated_multibyte_char() {
+        let opts = ContentIndexOptions {
+            enabled: true,
+            extensions: ["txt".to_string()].into_iter().collect(),
+            max_bytes: 2,
+        };
+
+        // 'é' encodes as two bytes (0xC3 0xA9); capping the read at 2 bytes
+        // lands mid-character and must not drop the whole read.
+        let path = std::env::temp_dir().join("pylocate_test_truncate_multibyte.txt");
+        std::fs::write(&path, "héllo world".as_bytes()).unwrap();
+
+        let content = read_indexable_content(&path, &opts);
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(content, Some("h".to_string()));
+    }
+}